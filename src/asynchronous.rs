@@ -0,0 +1,142 @@
+//! An async, connection-reusing counterpart to [`Client`](crate::Client), gated behind the
+//! `async` feature.
+use std::io::Cursor;
+use std::time::Duration;
+
+use bitcoin::{Block, BlockHash, bip158::BlockFilter, block::Header, consensus::Decodable};
+use futures::{Stream, stream};
+
+use crate::{
+    Endpoint, Timeout,
+    error::Error,
+    models::{Html, ServerStatus, TapTweaks},
+};
+
+/// The number of filters (or headers) returned per page by the server.
+const PAGE_SIZE: u32 = 2_000;
+
+/// An async client to request block data, reusing one connection pool across every request.
+///
+/// Mirrors the method surface of [`Client`](crate::Client) but returns futures, so a caller
+/// can overlap several round trips instead of paying their latency one at a time. Build one
+/// directly with [`new`](Self::new), or from a [`Builder`](crate::Builder) via
+/// [`build_async`](crate::Builder::build_async). Unlike `Client`, it does not fail over between
+/// several sources. See [`AsyncClient::filter_stream`] for pipelined filter pagination.
+#[derive(Debug, Clone)]
+pub struct AsyncClient<'e> {
+    endpoint: Endpoint<'e>,
+    timeout: Timeout,
+    http: reqwest::Client,
+}
+
+impl<'e> AsyncClient<'e> {
+    /// Build an async client for the given endpoint and timeout.
+    pub fn new(endpoint: Endpoint<'e>, timeout: Timeout) -> Self {
+        let http = reqwest::Client::builder()
+            .timeout(Duration::from_secs(timeout.0))
+            .build()
+            .expect("reqwest client configuration is valid");
+        Self {
+            endpoint,
+            timeout,
+            http,
+        }
+    }
+
+    /// The response timeout this client was built with.
+    pub fn timeout(&self) -> Timeout {
+        self.timeout
+    }
+
+    /// Return the root HTML of the server.
+    pub async fn index_html(&self) -> Result<Html, Error> {
+        let html = self
+            .http
+            .get(self.endpoint.0.to_string())
+            .send()
+            .await?
+            .text()
+            .await?;
+        Ok(Html(html))
+    }
+
+    /// Get the status of the server. See [`ServerStatus`] for the response structure.
+    pub async fn status(&self) -> Result<ServerStatus, Error> {
+        let status = self
+            .http
+            .get(self.endpoint.append_route("status"))
+            .send()
+            .await?
+            .json::<ServerStatus>()
+            .await?;
+        Ok(status)
+    }
+
+    /// Return up to 100,000 block headers starting from the specified height.
+    pub async fn block_headers(&self, start_height: u32) -> Result<Vec<Header>, Error> {
+        let route = self
+            .endpoint
+            .append_route(format!("headers/{start_height}"));
+        let bytes = self.http.get(route).send().await?.bytes().await?;
+        let mut headers = Vec::with_capacity(bytes.len() / 80);
+        for chunk in bytes.chunks_exact(80) {
+            headers.push(bitcoin::consensus::deserialize::<Header>(chunk)?);
+        }
+        Ok(headers)
+    }
+
+    /// Return up to 2,000 compact block filters starting from the specified height.
+    pub async fn filters(&self, start_height: u32) -> Result<Vec<BlockFilter>, Error> {
+        let route = self
+            .endpoint
+            .append_route(format!("filters/{start_height}"));
+        let bytes = self.http.get(route).send().await?.bytes().await?;
+        let mut cursor = Cursor::new(bytes.to_vec());
+        let mut filters = Vec::new();
+        while let Ok(bytes) = Vec::<u8>::consensus_decode_from_finite_reader(&mut cursor) {
+            filters.push(BlockFilter::new(&bytes));
+        }
+        Ok(filters)
+    }
+
+    /// Return up to 2,000 blocks of BIP-352 partial secrets (key tweaks).
+    pub async fn tweaks(&self, start_height: u32) -> Result<TapTweaks, Error> {
+        let route = self
+            .endpoint
+            .append_route(format!("sp/tweak-data/{start_height}"));
+        let tweaks = self
+            .http
+            .get(route)
+            .send()
+            .await?
+            .json::<TapTweaks>()
+            .await?;
+        Ok(tweaks)
+    }
+
+    /// Fetch the block by its hash.
+    pub async fn block(&self, block_hash: BlockHash) -> Result<Block, Error> {
+        let route = self.endpoint.append_route(format!("block/{block_hash}"));
+        let bytes = self.http.get(route).send().await?.bytes().await?;
+        let block = bitcoin::consensus::deserialize::<Block>(&bytes)?;
+        Ok(block)
+    }
+
+    /// Pipeline `filters()` requests from `start_height` up to (but not including)
+    /// `stop_height`, running up to `concurrency` requests at once while still yielding pages
+    /// in height order, so a full-chain sync overlaps network latency instead of paying it one
+    /// page at a time.
+    pub fn filter_stream(
+        &self,
+        start_height: u32,
+        stop_height: u32,
+        concurrency: usize,
+    ) -> impl Stream<Item = Result<Vec<BlockFilter>, Error>> + '_ {
+        use futures::StreamExt;
+
+        let page_heights = (start_height..stop_height).step_by(PAGE_SIZE as usize);
+        stream::iter(page_heights)
+            .map(move |height| self.filters(height))
+            .buffered(concurrency.max(1))
+    }
+}
@@ -0,0 +1,249 @@
+use bitcoin::{
+    BlockHash,
+    bip158::BlockFilter,
+    block::Header,
+    hashes::{Hash, HashEngine, sha256d},
+    pow::Target,
+};
+
+use crate::{Client, error::Error};
+
+/// Mainnet's difficulty adjustment interval, in blocks.
+const DIFFICULTY_ADJUSTMENT_INTERVAL: u32 = 2016;
+/// Mainnet's target timespan for a full adjustment interval, in seconds (two weeks).
+const POW_TARGET_TIMESPAN: u32 = 14 * 24 * 60 * 60;
+
+/// Validates a header chain and its paired BIP157 filter-header chain as pages stream in from
+/// a [`Client`], so a caller can trust data it never re-derives from a second source.
+///
+/// Each call to [`ChainValidator::sync_headers`] checks that every new header's
+/// `prev_blockhash` links to the running tip, that its hash meets the proof-of-work target its
+/// `bits` encode, and, at every 2,016-block retarget boundary, that the new target was derived
+/// from the previous period's timespan rather than picked arbitrarily by the server. The first
+/// header that breaks a rule yields [`Error::InvalidChain`] at its height.
+#[derive(Debug)]
+pub struct ChainValidator<'a, 'e> {
+    client: &'a Client<'e>,
+    height: u32,
+    tip: Option<Header>,
+    checkpoint: (u32, BlockHash),
+    period_start_time: Option<u32>,
+    filter_height: u32,
+    filter_header_tip: sha256d::Hash,
+}
+
+impl<'a, 'e> ChainValidator<'a, 'e> {
+    /// Start validating at `checkpoint_height`, trusting `checkpoint_hash` as the correct
+    /// block hash at that height (e.g. a hard-coded, well-known checkpoint), and fold filters
+    /// in starting at `filter_checkpoint_height`, trusting `filter_checkpoint_header` as the
+    /// correct running BIP157 filter header at that point.
+    ///
+    /// Without an externally trusted anchor, a first header is otherwise trusted to supply its
+    /// own proof-of-work target, so a malicious source could seed the whole chain with a
+    /// forged, trivially-easy "genesis" and every later link/PoW/retarget check would build on
+    /// it undetected. Pinning `checkpoint_height`/`checkpoint_hash` closes that gap: the header
+    /// at `checkpoint_height` is only accepted if it hashes to exactly `checkpoint_hash`, which
+    /// commits to that header's own `bits`.
+    ///
+    /// The filter-header chain has no notion of `checkpoint_height` (headers and filters can be
+    /// synced independently, and there's nothing that ties one's checkpoint to the other's), so
+    /// it needs its own anchor: pass `(0, sha256d::Hash::all_zeros())` to fold filters in from
+    /// genesis, or a trusted `(height, filter_header)` pair to start later, mirroring the header
+    /// checkpoint's trust model.
+    pub fn new(
+        client: &'a Client<'e>,
+        checkpoint_height: u32,
+        checkpoint_hash: BlockHash,
+        filter_checkpoint_height: u32,
+        filter_checkpoint_header: sha256d::Hash,
+    ) -> Self {
+        Self {
+            client,
+            height: checkpoint_height,
+            tip: None,
+            checkpoint: (checkpoint_height, checkpoint_hash),
+            period_start_time: None,
+            filter_height: filter_checkpoint_height,
+            filter_header_tip: filter_checkpoint_header,
+        }
+    }
+
+    /// The height of the next header to fetch and validate, one past the last header validated.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Fetch the next page of headers from the current height and validate each one in turn.
+    pub fn sync_headers(&mut self) -> Result<Vec<Header>, Error> {
+        let headers = self.client.block_headers(self.height)?;
+        for (offset, header) in headers.iter().enumerate() {
+            self.validate_header(self.height + offset as u32, header)?;
+        }
+        self.height += headers.len() as u32;
+        Ok(headers)
+    }
+
+    fn validate_header(&mut self, height: u32, header: &Header) -> Result<(), Error> {
+        if height == self.checkpoint.0 {
+            if header.block_hash() != self.checkpoint.1 {
+                return Err(Error::InvalidChain { height });
+            }
+        } else if let Some(tip) = self.tip {
+            if header.prev_blockhash != tip.block_hash() {
+                return Err(Error::InvalidChain { height });
+            }
+        }
+        let required_target = self.required_target(height, header);
+        header
+            .validate_pow(required_target)
+            .map_err(|_| Error::InvalidChain { height })?;
+        if height.is_multiple_of(DIFFICULTY_ADJUSTMENT_INTERVAL) {
+            self.period_start_time = Some(header.time);
+        }
+        self.tip = Some(*header);
+        Ok(())
+    }
+
+    /// The target a header at `height` must meet, recomputed from the previous retarget
+    /// period's timespan at every 2,016-block boundary and otherwise unchanged.
+    ///
+    /// The retarget math runs at reduced (128-bit) precision rather than full 256-bit target
+    /// arithmetic, without a big-integer dependency. The result is capped at mainnet's
+    /// proof-of-work limit and rounded through the compact `bits` encoding, as consensus does;
+    /// the dropped low bits of a mainnet target lie below that encoding's precision.
+    fn required_target(&self, height: u32, header: &Header) -> Target {
+        let Some(tip) = self.tip else {
+            return header.target();
+        };
+        if !height.is_multiple_of(DIFFICULTY_ADJUSTMENT_INTERVAL) {
+            return tip.target();
+        }
+        // The previous period's start time is only known once a full period has been observed
+        // from the checkpoint forward. Before that, there's nothing to compute a real ratio
+        // against, so the boundary is accepted unchanged rather than spuriously enforced
+        // against an uninitialized start time.
+        let Some(period_start_time) = self.period_start_time else {
+            return tip.target();
+        };
+        let actual_timespan = tip
+            .time
+            .saturating_sub(period_start_time)
+            .clamp(POW_TARGET_TIMESPAN / 4, POW_TARGET_TIMESPAN * 4) as u128;
+        let prev = target_to_u128(tip.target());
+        let scaled = prev.saturating_mul(actual_timespan) / POW_TARGET_TIMESPAN as u128;
+        let retarget =
+            Target::from_be_bytes(u128_to_target_bytes(scaled)).min(Target::MAX_ATTAINABLE_MAINNET);
+        Target::from_compact(retarget.to_compact_lossy())
+    }
+
+    /// Fold a page of downloaded filters into the running BIP157 filter-header chain.
+    ///
+    /// `best_filter_header`, from [`ServerStatus::best_filter_header`](crate::models::ServerStatus),
+    /// describes the chain *tip*, not whatever page was just folded in, so the comparison only
+    /// runs once this validator has folded in filters through `best_filter_height` — calling
+    /// this once per page while syncing returns `Ok(())` for every earlier page instead of
+    /// spuriously failing before the tip is reached.
+    pub fn verify_filter_headers(
+        &mut self,
+        filters: &[BlockFilter],
+        best_filter_height: u32,
+        best_filter_header: &str,
+    ) -> Result<(), Error> {
+        for filter in filters {
+            self.filter_header_tip = next_filter_header(filter, self.filter_header_tip);
+        }
+        self.filter_height += filters.len() as u32;
+        if self.filter_height <= best_filter_height {
+            return Ok(());
+        }
+        match best_filter_header.parse::<sha256d::Hash>() {
+            Ok(claimed) if claimed == self.filter_header_tip => Ok(()),
+            _ => Err(Error::InvalidChain {
+                height: self.filter_height - 1,
+            }),
+        }
+    }
+}
+
+/// Fold a downloaded filter into the BIP157 filter-header chain:
+/// `filter_header = sha256d(sha256d(filter) || prev_filter_header)`.
+fn next_filter_header(filter: &BlockFilter, prev: sha256d::Hash) -> sha256d::Hash {
+    let filter_hash = sha256d::Hash::hash(&filter.content);
+    let mut engine = sha256d::Hash::engine();
+    engine.input(filter_hash.as_byte_array());
+    engine.input(prev.as_byte_array());
+    sha256d::Hash::from_engine(engine)
+}
+
+fn target_to_u128(target: Target) -> u128 {
+    let bytes = target.to_be_bytes();
+    let mut high = [0u8; 16];
+    high.copy_from_slice(&bytes[0..16]);
+    u128::from_be_bytes(high)
+}
+
+fn u128_to_target_bytes(value: u128) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    bytes[0..16].copy_from_slice(&value.to_be_bytes());
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bitcoin::{TxMerkleNode, block::Version, pow::CompactTarget};
+
+    /// Mainnet's genesis `bits`: a round target whose only nonzero bytes fall within the
+    /// 128-bit window `required_target` operates on, so the reduced-precision round trip is
+    /// exact and these assertions aren't fighting rounding error.
+    const EASY_BITS: u32 = 0x1d00ffff;
+
+    fn header(time: u32) -> Header {
+        Header {
+            version: Version::from_consensus(1),
+            prev_blockhash: BlockHash::all_zeros(),
+            merkle_root: TxMerkleNode::all_zeros(),
+            time,
+            bits: CompactTarget::from_consensus(EASY_BITS),
+            nonce: 0,
+        }
+    }
+
+    fn validator_with_tip(tip: Header) -> ChainValidator<'static, 'static> {
+        ChainValidator {
+            client: Box::leak(Box::new(crate::Builder::new().build())),
+            height: 0,
+            tip: Some(tip),
+            checkpoint: (0, BlockHash::all_zeros()),
+            period_start_time: None,
+            filter_height: 0,
+            filter_header_tip: sha256d::Hash::all_zeros(),
+        }
+    }
+
+    #[test]
+    fn test_required_target_skips_ratio_before_period_observed() {
+        // A checkpoint that isn't itself on a retarget boundary means the previous period's
+        // start time was never observed. The first boundary hit afterward must not apply a
+        // bogus multiplier derived from an uninitialized start time.
+        let validator = validator_with_tip(header(1_000_000));
+        let target = validator.required_target(DIFFICULTY_ADJUSTMENT_INTERVAL, &header(1_000_100));
+        assert_eq!(target, validator.tip.unwrap().target());
+    }
+
+    #[test]
+    fn test_required_target_applies_ratio_once_period_known() {
+        let mut validator = validator_with_tip(header(POW_TARGET_TIMESPAN));
+        validator.period_start_time = Some(0);
+        // A timespan exactly equal to the target timespan leaves the target unchanged.
+        let target = validator.required_target(DIFFICULTY_ADJUSTMENT_INTERVAL, &header(POW_TARGET_TIMESPAN));
+        assert_eq!(target, validator.tip.unwrap().target());
+    }
+
+    #[test]
+    fn test_required_target_unchanged_off_boundary() {
+        let validator = validator_with_tip(header(0));
+        let target = validator.required_target(DIFFICULTY_ADJUSTMENT_INTERVAL + 1, &header(1));
+        assert_eq!(target, validator.tip.unwrap().target());
+    }
+}
@@ -7,6 +7,22 @@ pub enum Error {
     Decoder(bitcoin::consensus::encode::Error),
     /// Underlying HTTPs request failed.
     Request(bitreq::Error),
+    /// A BIP158 filter could not be matched against the watch-list.
+    Filter(bitcoin::bip158::Error),
+    /// The header chain or filter-header chain broke a validation rule at the given height.
+    InvalidChain {
+        /// The first height at which validation failed.
+        height: u32,
+    },
+    /// An elliptic-curve operation failed, for example a scalar tweak landing out of range.
+    Secp(bitcoin::secp256k1::Error),
+    /// A server-supplied public key (e.g. a partial secret from [`tweaks`](crate::Client::tweaks))
+    /// was not a valid hex-encoded public key.
+    Key(bitcoin::key::ParsePublicKeyError),
+    /// Underlying async HTTPs request failed. Only produced by
+    /// [`AsyncClient`](crate::asynchronous::AsyncClient).
+    #[cfg(feature = "async")]
+    AsyncRequest(reqwest::Error),
 }
 
 impl fmt::Display for Error {
@@ -14,6 +30,14 @@ impl fmt::Display for Error {
         match self {
             Error::Decoder(e) => write!(f, "consensus error {e}"),
             Error::Request(e) => write!(f, "request error {e}"),
+            Error::Filter(e) => write!(f, "filter error {e}"),
+            Error::InvalidChain { height } => {
+                write!(f, "chain validation failed at height {height}")
+            }
+            Error::Secp(e) => write!(f, "secp256k1 error {e}"),
+            Error::Key(e) => write!(f, "public key error {e}"),
+            #[cfg(feature = "async")]
+            Error::AsyncRequest(e) => write!(f, "request error {e}"),
         }
     }
 }
@@ -30,11 +54,42 @@ impl From<bitcoin::consensus::encode::Error> for Error {
     }
 }
 
+impl From<bitcoin::bip158::Error> for Error {
+    fn from(value: bitcoin::bip158::Error) -> Self {
+        Self::Filter(value)
+    }
+}
+
+impl From<bitcoin::secp256k1::Error> for Error {
+    fn from(value: bitcoin::secp256k1::Error) -> Self {
+        Self::Secp(value)
+    }
+}
+
+impl From<bitcoin::key::ParsePublicKeyError> for Error {
+    fn from(value: bitcoin::key::ParsePublicKeyError) -> Self {
+        Self::Key(value)
+    }
+}
+
+#[cfg(feature = "async")]
+impl From<reqwest::Error> for Error {
+    fn from(value: reqwest::Error) -> Self {
+        Self::AsyncRequest(value)
+    }
+}
+
 impl std::error::Error for Error {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
             Self::Decoder(d) => Some(d),
             Self::Request(r) => Some(r),
+            Self::Filter(f) => Some(f),
+            Self::InvalidChain { .. } => None,
+            Self::Secp(e) => Some(e),
+            Self::Key(e) => Some(e),
+            #[cfg(feature = "async")]
+            Self::AsyncRequest(e) => Some(e),
         }
     }
 }
@@ -1,20 +1,36 @@
 //! A Rust client for [`block-dn`](https://github.com/guggero/block-dn#).
 #![warn(missing_docs)]
-use std::{borrow::Cow, io::Cursor, net::SocketAddr};
+use std::{
+    borrow::Cow,
+    net::SocketAddr,
+    sync::atomic::{AtomicUsize, Ordering},
+};
 
-use bitcoin::{Block, BlockHash, bip158::BlockFilter, block::Header, consensus::Decodable};
-use models::{Html, ServerStatus, TapTweaks};
+use bitcoin::{Block, BlockHash, bip158::BlockFilter, block::Header};
+use models::{FeeHistory, Html, ServerStatus, TapTweaks};
 
+/// An async, connection-reusing client variant. Requires the `async` feature.
+#[cfg(feature = "async")]
+pub mod asynchronous;
+/// Header-chain and filter-header chain validation.
+pub mod chain;
 /// Errors that may occur when querying.
 pub mod error;
 /// Data models for server queries and responses.
 pub mod models;
+/// BIP158 filter matching against a watch-list of scripts.
+pub mod scan;
+/// BIP-352 silent-payment scanning built on the tweak endpoint.
+pub mod silent_payments;
+/// The [`BlockSource`] trait and the machinery that fails over between several of them.
+pub mod source;
 
 use crate::error::Error;
+use crate::source::{BlockSource, EndpointSource};
 
 /// An endpoint for a `block-dn` server.
 #[derive(Debug, Clone)]
-pub struct Endpoint<'e>(Cow<'e, str>);
+pub struct Endpoint<'e>(pub(crate) Cow<'e, str>);
 
 impl<'e> Endpoint<'e> {
     /// The original `block-dn` server hosted at `block-dn.org`.
@@ -23,6 +39,8 @@ impl<'e> Endpoint<'e> {
     // pub const TAPROOT_DN: Self = Self(Cow::Borrowed("https://taprootdn.xyz"));
     /// Local host at port 8080.
     pub const LOCAL_HOST: Self = Self(Cow::Borrowed("https://127.0.0.1:8080"));
+    /// The `block-dn`-compatible server hosted at `2140.dev`.
+    pub const DEV_2140: Self = Self(Cow::Borrowed("https://2140.dev"));
 
     /// Use your self-hosted `block-dn` instance.
     pub fn from_custom_domain(other: &'static str) -> Self {
@@ -36,14 +54,14 @@ impl<'e> Endpoint<'e> {
     }
 
     /// Append a route to the endpoint.
-    fn append_route(&self, hook: impl AsRef<str>) -> String {
+    pub(crate) fn append_route(&self, hook: impl AsRef<str>) -> String {
         format!("{}/{}", self.0, hook.as_ref())
     }
 }
 
 /// The response timeout permitted.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
-pub struct Timeout(u64);
+pub struct Timeout(pub(crate) u64);
 
 impl Timeout {
     /// Build a timeout from number of seconds.
@@ -61,7 +79,7 @@ impl Default for Timeout {
 /// Build a new client to query data for.
 #[derive(Debug)]
 pub struct Builder<'e> {
-    endpoint: Endpoint<'e>,
+    endpoints: Vec<Endpoint<'e>>,
     timeout: Timeout,
 }
 
@@ -69,7 +87,7 @@ impl<'e> Builder<'e> {
     /// Create a new builder [`ClientBuilder`].
     pub fn new() -> Self {
         Self {
-            endpoint: Endpoint::BLOCK_DN_ORG,
+            endpoints: vec![Endpoint::BLOCK_DN_ORG],
             timeout: Timeout::default(),
         }
     }
@@ -80,18 +98,47 @@ impl<'e> Builder<'e> {
         self
     }
 
-    /// Add an endpoint to query.
+    /// Set the endpoint to query, replacing any previously configured endpoints.
     pub fn endpoint(mut self, endpoint: Endpoint<'e>) -> Self {
-        self.endpoint = endpoint;
+        self.endpoints = vec![endpoint];
         self
     }
 
+    /// Add a fallback endpoint, tried in the order added after earlier endpoints return
+    /// [`Error::Request`].
+    pub fn add_endpoint(mut self, endpoint: Endpoint<'e>) -> Self {
+        self.endpoints.push(endpoint);
+        self
+    }
+
+    /// Build an [`AsyncClient`](asynchronous::AsyncClient) from the configuration.
+    ///
+    /// [`AsyncClient`](asynchronous::AsyncClient) does not yet support failover between several
+    /// sources, so only the first configured endpoint is used; any endpoints added with
+    /// [`add_endpoint`](Self::add_endpoint) are ignored.
+    #[cfg(feature = "async")]
+    pub fn build_async(self) -> asynchronous::AsyncClient<'e> {
+        let endpoint = self
+            .endpoints
+            .into_iter()
+            .next()
+            .expect("a builder always has at least one endpoint");
+        asynchronous::AsyncClient::new(endpoint, self.timeout)
+    }
+
     /// Build a [`Client`] from the configuration.
     pub fn build(self) -> Client<'e> {
-        Client {
-            endpoint: self.endpoint,
-            timeout: self.timeout,
-        }
+        let sources = self
+            .endpoints
+            .into_iter()
+            .map(|endpoint| {
+                Box::new(EndpointSource {
+                    endpoint,
+                    timeout: self.timeout,
+                }) as Box<dyn BlockSource + 'e>
+            })
+            .collect();
+        Client::from_sources(sources)
     }
 }
 
@@ -102,73 +149,101 @@ impl<'e> Default for Builder<'e> {
 }
 
 /// A client to request block data.
-#[derive(Debug)]
+///
+/// Queries a priority-ordered list of [`BlockSource`]s, skipping a source that returns
+/// [`Error::Request`] and surfacing the last error only once every source has been exhausted.
+/// The most recently healthy source is remembered so later calls don't re-probe a dead one.
 pub struct Client<'e> {
-    endpoint: Endpoint<'e>,
-    timeout: Timeout,
+    sources: Vec<Box<dyn BlockSource + 'e>>,
+    last_healthy: AtomicUsize,
+}
+
+impl std::fmt::Debug for Client<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Client")
+            .field("sources", &self.sources)
+            .field("last_healthy", &self.last_healthy.load(Ordering::Relaxed))
+            .finish()
+    }
 }
 
 impl<'e> Client<'e> {
-    const EXPECTED_HEADER_LIST_SIZE: usize = 100_000;
+    /// Build a client directly from an ordered list of [`BlockSource`]s. Use this to mix
+    /// custom transports, such as a local Bitcoin Core REST proxy, in with `block-dn`
+    /// endpoints built by [`Builder`].
+    ///
+    /// # Panics
+    ///
+    /// If `sources` is empty.
+    pub fn from_sources(sources: Vec<Box<dyn BlockSource + 'e>>) -> Self {
+        assert!(!sources.is_empty(), "a client needs at least one source");
+        Self {
+            sources,
+            last_healthy: AtomicUsize::new(0),
+        }
+    }
+
+    /// Try each source in priority order, starting from the last known healthy one, skipping
+    /// over a [`Error::Request`] failure and returning the last error once all sources fail.
+    fn query<T>(&self, f: impl Fn(&dyn BlockSource) -> Result<T, Error>) -> Result<T, Error> {
+        let len = self.sources.len();
+        let start = self.last_healthy.load(Ordering::Relaxed);
+        let mut last_err = None;
+        for offset in 0..len {
+            let index = (start + offset) % len;
+            match f(self.sources[index].as_ref()) {
+                Ok(value) => {
+                    self.last_healthy.store(index, Ordering::Relaxed);
+                    return Ok(value);
+                }
+                Err(Error::Request(e)) => last_err = Some(Error::Request(e)),
+                Err(other) => return Err(other),
+            }
+        }
+        Err(last_err.expect("a client has at least one source"))
+    }
+
     /// Return the root HTML of the server.
     pub fn index_html(&self) -> Result<Html, Error> {
-        let response = bitreq::get(self.endpoint.0.to_string())
-            .with_timeout(self.timeout.0)
-            .send()?;
-        let html = response.as_str()?;
-        Ok(Html(html.to_string()))
+        self.query(|source| source.index_html())
     }
 
     /// Get the status of the server. See [`ServerStatus`] for the response structure.
     pub fn status(&self) -> Result<ServerStatus, Error> {
-        let status = bitreq::get(self.endpoint.append_route("status"))
-            .with_timeout(self.timeout.0)
-            .send()?;
-        Ok(status.json::<ServerStatus>()?)
+        self.query(|source| source.status())
     }
 
     /// Return up to 100,000 block headers starting from the specified height.
     pub fn block_headers(&self, start_height: u32) -> Result<Vec<Header>, Error> {
-        let route = self
-            .endpoint
-            .append_route(format!("headers/{start_height}"));
-        let response = bitreq::get(route).with_timeout(self.timeout.0).send()?;
-        let mut headers = Vec::with_capacity(Self::EXPECTED_HEADER_LIST_SIZE * 80);
-        for chunk in response.as_bytes().chunks_exact(80) {
-            headers.push(bitcoin::consensus::deserialize::<Header>(chunk)?);
-        }
-        Ok(headers)
+        self.query(|source| source.block_headers(start_height))
     }
 
     /// Return up to 2,000 compact block filters starting from the specified height.
     pub fn filters(&self, start_height: u32) -> Result<Vec<BlockFilter>, Error> {
-        let route = self
-            .endpoint
-            .append_route(format!("filters/{start_height}"));
-        let response = bitreq::get(route).with_timeout(self.timeout.0).send()?;
-        let mut cursor = Cursor::new(response.into_bytes());
-        let mut filters = Vec::new();
-        while let Ok(bytes) = Vec::<u8>::consensus_decode_from_finite_reader(&mut cursor) {
-            filters.push(BlockFilter::new(&bytes));
-        }
-        Ok(filters)
+        self.query(|source| source.filters(start_height))
     }
 
     /// Return up to 2,000 blocks of BIP-352 partial secrets (key tweaks).
     pub fn tweaks(&self, start_height: u32) -> Result<TapTweaks, Error> {
-        let route = self
-            .endpoint
-            .append_route(format!("sp/tweak-data/{start_height}"));
-        let response = bitreq::get(route).with_timeout(self.timeout.0).send()?;
-        Ok(response.json::<TapTweaks>()?)
+        self.query(|source| source.tweaks(start_height))
     }
 
     /// Fetch the block by its hash.
     pub fn block(&self, block_hash: BlockHash) -> Result<Block, Error> {
-        let route = self.endpoint.append_route(format!("block/{block_hash}"));
-        let response = bitreq::get(route).with_timeout(self.timeout.0).send()?;
-        let block = bitcoin::consensus::deserialize::<Block>(response.as_bytes())?;
-        Ok(block)
+        self.query(|source| source.block(block_hash))
+    }
+
+    /// Estimate the feerate, in satoshis per virtual byte, needed to confirm within the
+    /// requested number of blocks.
+    pub fn estimate_smart_fee(&self, blocks: u32) -> Result<f64, Error> {
+        self.query(|source| source.estimate_smart_fee(blocks))
+    }
+
+    /// Return the estimated feerate and requested feerate percentiles for each of the most
+    /// recent `block_count` blocks, so a wallet can present a fee slider instead of picking
+    /// from a single point estimate. See [`FeeHistory`].
+    pub fn fee_history(&self, block_count: u32, percentiles: &[f64]) -> Result<FeeHistory, Error> {
+        self.query(|source| source.fee_history(block_count, percentiles))
     }
 }
 
@@ -176,7 +251,7 @@ impl<'e> Client<'e> {
 mod tests {
     use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 
-    use crate::Endpoint;
+    use crate::{Client, Endpoint};
 
     #[test]
     fn test_endpoint() {
@@ -185,4 +260,14 @@ mod tests {
         let filters_route = endpoint.append_route("filters/0");
         assert_eq!(filters_route.as_str(), "https://8.8.8.8:8080/filters/0");
     }
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    /// `Client` is shared across threads behind an `Arc` by callers, e.g. a wallet's sync and
+    /// fee-estimation tasks. It must stay `Send + Sync` regardless of how many `BlockSource`s
+    /// back it or how failover state is tracked internally.
+    #[test]
+    fn test_client_is_send_sync() {
+        assert_send_sync::<Client<'static>>();
+    }
 }
@@ -2,10 +2,32 @@ use std::collections::BTreeMap;
 
 use bitcoin::PublicKey;
 
+use crate::error::Error;
+
 /// A string representing HTML. Suitable to render on a webpage.
 #[derive(Debug)]
 pub struct Html(pub String);
 
+/// A smart fee estimate for confirmation within a requested number of blocks.
+#[derive(Debug, Clone, Copy, serde::Deserialize)]
+pub struct EstimateSmartFee {
+    /// The estimated feerate, in satoshis per virtual byte.
+    pub feerate: f64,
+}
+
+/// Per-block feerates over a recent window, extending [`EstimateSmartFee`] with a
+/// distribution so a wallet can present a fee slider instead of a single point estimate.
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct FeeHistory {
+    /// Heights of the most recent blocks covered by this response, oldest first.
+    pub heights: Vec<u32>,
+    /// The estimated feerate for each block in `heights`, in satoshis per virtual byte.
+    pub feerates: Vec<f64>,
+    /// The requested feerate percentiles, in satoshis per virtual byte, for each block in
+    /// `heights`, indexed the same way as `heights`.
+    pub percentiles: Vec<Vec<f64>>,
+}
+
 /// The status of the server.
 #[derive(Debug, Clone, serde::Deserialize)]
 pub struct ServerStatus {
@@ -46,18 +68,17 @@ pub struct TapTweaks {
 
 impl TapTweaks {
     /// Convert the response into an iterator of blocks with transaction index and corresponding
-    /// public key.
-    ///
-    /// # Panics
-    ///
-    /// If the partial secret is not a valid hex encoding of a public key.
-    pub fn fallible_into_iterator(self) -> impl Iterator<Item = Option<BTreeMap<u32, PublicKey>>> {
+    /// public key, yielding an [`Error`] for a block whose partial secret is not a valid
+    /// hex-encoded public key rather than panicking on server-supplied data.
+    pub fn fallible_into_iterator(
+        self,
+    ) -> impl Iterator<Item = Option<Result<BTreeMap<u32, PublicKey>, Error>>> {
         self.blocks.into_iter().map(|tweaks| {
             tweaks.map(|tweaks| {
                 tweaks
                     .into_iter()
-                    .map(|(tx_index, pk_str)| (tx_index, pk_str.parse::<PublicKey>().unwrap()))
-                    .collect::<BTreeMap<u32, PublicKey>>()
+                    .map(|(tx_index, pk_str)| Ok((tx_index, pk_str.parse::<PublicKey>()?)))
+                    .collect::<Result<BTreeMap<u32, PublicKey>, Error>>()
             })
         })
     }
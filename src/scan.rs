@@ -0,0 +1,80 @@
+use bitcoin::{Block, BlockHash, ScriptBuf};
+
+use crate::{Client, error::Error};
+
+/// A block height and hash at which a watched script matched a BIP158 filter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScanMatch {
+    /// The height of the matching block.
+    pub height: u32,
+    /// The hash of the matching block.
+    pub block_hash: BlockHash,
+}
+
+/// Scans BIP158 compact filters for a watch-list of scripts over a range of block heights.
+///
+/// Pages through `filters()` and `block_headers()` together so each filter is matched under
+/// the SipHash key derived from its own block hash, turning a raw byte fetcher into a usable
+/// SPV wallet backend.
+#[derive(Debug)]
+pub struct Scanner<'a, 'e> {
+    client: &'a Client<'e>,
+    watch: Vec<ScriptBuf>,
+}
+
+impl<'a, 'e> Scanner<'a, 'e> {
+    /// Create a scanner for the given client and watch-list of scripts.
+    pub fn new(client: &'a Client<'e>, watch: Vec<ScriptBuf>) -> Self {
+        Self { client, watch }
+    }
+
+    /// Scan `[start_height, stop_height)` for filter matches against the watch-list, paging
+    /// through filters and headers 2,000 heights at a time.
+    pub fn scan(&self, start_height: u32, stop_height: u32) -> Result<Vec<ScanMatch>, Error> {
+        let mut matches = Vec::new();
+        let mut height = start_height;
+        let queries = self
+            .watch
+            .iter()
+            .map(|script| script.as_bytes())
+            .collect::<Vec<_>>();
+        while height < stop_height {
+            let headers = self.client.block_headers(height)?;
+            let filters = self.client.filters(height)?;
+            let page_len = filters
+                .len()
+                .min(headers.len())
+                .min((stop_height - height) as usize);
+            if page_len == 0 {
+                break;
+            }
+            for (offset, filter) in filters.iter().take(page_len).enumerate() {
+                let block_hash = headers[offset].block_hash();
+                if filter.match_any(&block_hash, queries.iter().copied())? {
+                    matches.push(ScanMatch {
+                        height: height + offset as u32,
+                        block_hash,
+                    });
+                }
+            }
+            height += page_len as u32;
+        }
+        Ok(matches)
+    }
+
+    /// Convenience wrapper over [`Scanner::scan`] that also fetches the full [`Block`] for
+    /// each match.
+    pub fn scan_blocks(
+        &self,
+        start_height: u32,
+        stop_height: u32,
+    ) -> Result<Vec<(ScanMatch, Block)>, Error> {
+        self.scan(start_height, stop_height)?
+            .into_iter()
+            .map(|found| {
+                let block = self.client.block(found.block_hash)?;
+                Ok((found, block))
+            })
+            .collect()
+    }
+}
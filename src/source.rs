@@ -0,0 +1,133 @@
+use std::io::Cursor;
+
+use bitcoin::{Block, BlockHash, bip158::BlockFilter, block::Header, consensus::Decodable};
+
+use crate::{
+    Endpoint, Timeout,
+    error::Error,
+    models::{EstimateSmartFee, FeeHistory, Html, ServerStatus, TapTweaks},
+};
+
+/// A source of block data that may be queried for chain state, filters, and raw blocks.
+///
+/// Implement this trait to plug in a custom transport (for example, a local Bitcoin Core REST
+/// proxy) and hand it to [`Client::from_sources`](crate::Client::from_sources) so the client's
+/// failover logic treats it the same as a `block-dn` [`Endpoint`]. Bound by `Send + Sync` so a
+/// [`Client`](crate::Client) built from sources stays shareable across threads, matching the
+/// plain-`Endpoint`-backed client it replaced.
+pub trait BlockSource: std::fmt::Debug + Send + Sync {
+    /// Return the root HTML of the server.
+    fn index_html(&self) -> Result<Html, Error>;
+
+    /// Get the status of the server. See [`ServerStatus`] for the response structure.
+    fn status(&self) -> Result<ServerStatus, Error>;
+
+    /// Return up to 100,000 block headers starting from the specified height.
+    fn block_headers(&self, start_height: u32) -> Result<Vec<Header>, Error>;
+
+    /// Return up to 2,000 compact block filters starting from the specified height.
+    fn filters(&self, start_height: u32) -> Result<Vec<BlockFilter>, Error>;
+
+    /// Return up to 2,000 blocks of BIP-352 partial secrets (key tweaks).
+    fn tweaks(&self, start_height: u32) -> Result<TapTweaks, Error>;
+
+    /// Fetch the block by its hash.
+    fn block(&self, block_hash: BlockHash) -> Result<Block, Error>;
+
+    /// Estimate the feerate, in satoshis per virtual byte, needed to confirm within the
+    /// requested number of blocks.
+    fn estimate_smart_fee(&self, blocks: u32) -> Result<f64, Error>;
+
+    /// Return the estimated feerate and requested feerate percentiles for each of the most
+    /// recent `block_count` blocks.
+    fn fee_history(&self, block_count: u32, percentiles: &[f64]) -> Result<FeeHistory, Error>;
+}
+
+/// A [`BlockSource`] backed by a single `block-dn` HTTP [`Endpoint`].
+#[derive(Debug)]
+pub(crate) struct EndpointSource<'e> {
+    pub(crate) endpoint: Endpoint<'e>,
+    pub(crate) timeout: Timeout,
+}
+
+impl EndpointSource<'_> {
+    const EXPECTED_HEADER_LIST_SIZE: usize = 100_000;
+}
+
+impl BlockSource for EndpointSource<'_> {
+    fn index_html(&self) -> Result<Html, Error> {
+        let response = bitreq::get(self.endpoint.0.to_string())
+            .with_timeout(self.timeout.0)
+            .send()?;
+        let html = response.as_str()?;
+        Ok(Html(html.to_string()))
+    }
+
+    fn status(&self) -> Result<ServerStatus, Error> {
+        let status = bitreq::get(self.endpoint.append_route("status"))
+            .with_timeout(self.timeout.0)
+            .send()?;
+        Ok(status.json::<ServerStatus>()?)
+    }
+
+    fn block_headers(&self, start_height: u32) -> Result<Vec<Header>, Error> {
+        let route = self
+            .endpoint
+            .append_route(format!("headers/{start_height}"));
+        let response = bitreq::get(route).with_timeout(self.timeout.0).send()?;
+        let mut headers = Vec::with_capacity(Self::EXPECTED_HEADER_LIST_SIZE * 80);
+        for chunk in response.as_bytes().chunks_exact(80) {
+            headers.push(bitcoin::consensus::deserialize::<Header>(chunk)?);
+        }
+        Ok(headers)
+    }
+
+    fn filters(&self, start_height: u32) -> Result<Vec<BlockFilter>, Error> {
+        let route = self
+            .endpoint
+            .append_route(format!("filters/{start_height}"));
+        let response = bitreq::get(route).with_timeout(self.timeout.0).send()?;
+        let mut cursor = Cursor::new(response.into_bytes());
+        let mut filters = Vec::new();
+        while let Ok(bytes) = Vec::<u8>::consensus_decode_from_finite_reader(&mut cursor) {
+            filters.push(BlockFilter::new(&bytes));
+        }
+        Ok(filters)
+    }
+
+    fn tweaks(&self, start_height: u32) -> Result<TapTweaks, Error> {
+        let route = self
+            .endpoint
+            .append_route(format!("sp/tweak-data/{start_height}"));
+        let response = bitreq::get(route).with_timeout(self.timeout.0).send()?;
+        Ok(response.json::<TapTweaks>()?)
+    }
+
+    fn block(&self, block_hash: BlockHash) -> Result<Block, Error> {
+        let route = self.endpoint.append_route(format!("block/{block_hash}"));
+        let response = bitreq::get(route).with_timeout(self.timeout.0).send()?;
+        let block = bitcoin::consensus::deserialize::<Block>(response.as_bytes())?;
+        Ok(block)
+    }
+
+    fn estimate_smart_fee(&self, blocks: u32) -> Result<f64, Error> {
+        let route = self
+            .endpoint
+            .append_route(format!("fee/estimate-smart/{blocks}"));
+        let response = bitreq::get(route).with_timeout(self.timeout.0).send()?;
+        Ok(response.json::<EstimateSmartFee>()?.feerate)
+    }
+
+    fn fee_history(&self, block_count: u32, percentiles: &[f64]) -> Result<FeeHistory, Error> {
+        let percentiles = percentiles
+            .iter()
+            .map(|percentile| percentile.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        let route = self
+            .endpoint
+            .append_route(format!("fee/history/{block_count}?percentiles={percentiles}"));
+        let response = bitreq::get(route).with_timeout(self.timeout.0).send()?;
+        Ok(response.json::<FeeHistory>()?)
+    }
+}
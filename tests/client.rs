@@ -1,4 +1,5 @@
-use block_dn_client::{Builder, Client, Endpoint, Timeout};
+use bitcoin::hashes::Hash;
+use block_dn_client::{Builder, Client, Endpoint, Timeout, chain::ChainValidator, scan::Scanner};
 
 fn default_client() -> Client<'static> {
     Builder::default().build()
@@ -56,3 +57,36 @@ fn test_estimate_fee() {
     let client = Builder::new().endpoint(Endpoint::DEV_2140).build();
     assert!(client.estimate_smart_fee(1).is_ok());
 }
+
+#[test]
+fn test_scanner_scans_small_range() {
+    let client = default_client();
+    let scanner = Scanner::new(&client, vec![bitcoin::ScriptBuf::new()]);
+    assert!(scanner.scan(0, 10).is_ok());
+}
+
+#[test]
+fn test_chain_validator_syncs_from_genesis() {
+    let client = default_client();
+    let genesis_hash = "000000000019d6689c085ae165831e934ff763ae46a2a6c172b3f1b60a8ce26f"
+        .parse()
+        .unwrap();
+    let mut validator = ChainValidator::new(
+        &client,
+        0,
+        genesis_hash,
+        0,
+        bitcoin::hashes::sha256d::Hash::all_zeros(),
+    );
+    assert!(validator.sync_headers().is_ok());
+}
+
+#[test]
+#[cfg(feature = "async")]
+fn test_build_async_uses_builder_timeout() {
+    let async_client = Builder::new()
+        .timeout(Timeout::from_seconds(5))
+        .add_endpoint(Endpoint::DEV_2140)
+        .build_async();
+    assert_eq!(async_client.timeout(), Timeout::from_seconds(5));
+}